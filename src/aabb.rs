@@ -0,0 +1,86 @@
+use crate::ray::Ray;
+use crate::util::*;
+
+/// An axis-aligned bounding box, used to quickly reject rays that cannot
+/// possibly hit a hittable before doing its full intersection test.
+#[derive(Clone, Copy)]
+pub struct Aabb {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Aabb {
+    pub fn new(min: Point3, max: Point3) -> Self {
+        Self { min, max }
+    }
+
+    /// Test whether the ray passes through this box within `[t_min, t_max]`,
+    /// using the slab method.
+    pub fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> bool {
+        let mut t_min = t_min;
+        let mut t_max = t_max;
+
+        for axis in 0..3 {
+            let inv_d = 1.0 / r.direction[axis];
+            let mut t0 = (self.min[axis] - r.origin[axis]) * inv_d;
+            let mut t1 = (self.max[axis] - r.origin[axis]) * inv_d;
+            if inv_d < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t0.max(t_min);
+            t_max = t1.min(t_max);
+            if t_max <= t_min {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The smallest box containing both `self` and `other`.
+    pub fn surrounding_box(&self, other: &Aabb) -> Aabb {
+        let min = Point3::new(
+            self.min.x.min(other.min.x),
+            self.min.y.min(other.min.y),
+            self.min.z.min(other.min.z),
+        );
+        let max = Point3::new(
+            self.max.x.max(other.max.x),
+            self.max.y.max(other.max.y),
+            self.max.z.max(other.max.z),
+        );
+        Aabb::new(min, max)
+    }
+
+    /// The midpoint of the box along the given axis (0 = x, 1 = y, 2 = z).
+    pub fn centroid(&self, axis: usize) -> f64 {
+        (self.min[axis] + self.max[axis]) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hit_ray_through_box() {
+        let bbox = Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+        let r = Ray::new(Point3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 1.0), 0.0);
+        assert!(bbox.hit(&r, 0.0, f64::INFINITY));
+    }
+
+    #[test]
+    fn hit_ray_missing_box() {
+        let bbox = Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+        let r = Ray::new(Point3::new(5.0, 5.0, -5.0), Vec3::new(0.0, 0.0, 1.0), 0.0);
+        assert!(!bbox.hit(&r, 0.0, f64::INFINITY));
+    }
+
+    #[test]
+    fn surrounding_box_unions_both_boxes() {
+        let a = Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(0.0, 0.0, 0.0));
+        let b = Aabb::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+        let u = a.surrounding_box(&b);
+        assert_eq!(u.min, Point3::new(-1.0, -1.0, -1.0));
+        assert_eq!(u.max, Point3::new(1.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::aabb::Aabb;
+use crate::hit::{Hit, Hittable};
+use crate::ray::Ray;
+use crate::util::*;
+use anyhow::{bail, Result};
+
+/// A node in a bounding volume hierarchy. Each node tests the ray against
+/// its own bounding box first, so whole subtrees can be skipped without
+/// testing every object they contain.
+pub struct BvhNode {
+    left: Box<dyn Hittable>,
+    right: Option<Box<dyn Hittable>>,
+    bbox: Aabb,
+}
+
+impl BvhNode {
+    /// Build a BVH over the given objects by recursively partitioning them
+    /// on a randomly chosen axis. Errors if `hittables` is empty, since a
+    /// BVH node always needs at least one object to bound.
+    pub fn new(mut hittables: Vec<Box<dyn Hittable>>) -> Result<Self> {
+        if hittables.is_empty() {
+            bail!("cannot build a BvhNode over an empty object list");
+        }
+
+        let axis = (uniform() * 3.0) as usize;
+        hittables.sort_by(|a, b| {
+            let centroid_a = a
+                .bounding_box()
+                .expect("hittable has no bounding box")
+                .centroid(axis);
+            let centroid_b = b
+                .bounding_box()
+                .expect("hittable has no bounding box")
+                .centroid(axis);
+            centroid_a.partial_cmp(&centroid_b).unwrap()
+        });
+
+        if hittables.len() == 1 {
+            let left = hittables.pop().unwrap();
+            let bbox = left.bounding_box().expect("hittable has no bounding box");
+            return Ok(Self {
+                left,
+                right: None,
+                bbox,
+            });
+        }
+
+        let right_half = hittables.split_off(hittables.len() / 2);
+        let left: Box<dyn Hittable> = Box::new(BvhNode::new(hittables)?);
+        let right: Box<dyn Hittable> = Box::new(BvhNode::new(right_half)?);
+        let bbox = left
+            .bounding_box()
+            .unwrap()
+            .surrounding_box(&right.bounding_box().unwrap());
+
+        Ok(Self {
+            left,
+            right: Some(right),
+            bbox,
+        })
+    }
+}
+
+impl Hittable for BvhNode {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<Hit> {
+        if !self.bbox.hit(r, t_min, t_max) {
+            return None;
+        }
+
+        let hit_left = self.left.hit(r, t_min, t_max);
+        let t_max = hit_left.as_ref().map_or(t_max, |h| h.t);
+        let hit_right = self
+            .right
+            .as_ref()
+            .and_then(|right| right.hit(r, t_min, t_max));
+
+        hit_right.or(hit_left)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hit::Sphere;
+    use crate::material::MatteFinish;
+    use std::sync::Arc;
+
+    fn sphere(center: Point3, radius: f64) -> Box<dyn Hittable> {
+        Box::new(Sphere {
+            center,
+            radius,
+            material: Arc::new(MatteFinish {
+                albedo: Color::new(0.5, 0.5, 0.5),
+            }),
+        })
+    }
+
+    #[test]
+    fn new_rejects_empty_input() {
+        assert!(BvhNode::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn new_wraps_a_single_object() {
+        let bvh = BvhNode::new(vec![sphere(Point3::new(0.0, 0.0, 0.0), 1.0)]).unwrap();
+        let bbox = bvh.bounding_box().unwrap();
+        assert_eq!(bbox.min, Point3::new(-1.0, -1.0, -1.0));
+        assert_eq!(bbox.max, Point3::new(1.0, 1.0, 1.0));
+    }
+}
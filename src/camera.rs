@@ -10,9 +10,12 @@ pub struct Camera {
     v: Vec3,
     w: Vec3,
     lens_radius: f64,
+    time0: f64,
+    time1: f64,
 }
 
 impl Camera {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         lookfrom: Point3,
         lookat: Point3,
@@ -20,6 +23,8 @@ impl Camera {
         vfov_radians: f64,
         aspect_ratio: f64,
         aperture: f64,
+        time0: f64,
+        time1: f64,
     ) -> Camera {
         let h = (vfov_radians / 2.0).tan();
         let viewport_height = 2.0 * h;
@@ -44,16 +49,20 @@ impl Camera {
             w,
             lower_left_corner,
             lens_radius: aperture / 2.0,
+            time0,
+            time1,
         }
     }
 
     pub fn get_ray(&self, u: f64, v: f64) -> Ray {
         let rd = self.lens_radius * random_in_unit_disc();
         let offset = self.u * rd.x + self.v * rd.y;
+        let time = self.time0 + uniform() * (self.time1 - self.time0);
 
         Ray::new(
             self.origin + offset,
             self.lower_left_corner + self.horizontal * u + self.vertical * v - self.origin - offset,
+            time,
         )
     }
 }
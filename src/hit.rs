@@ -1,7 +1,8 @@
+use crate::aabb::Aabb;
 use crate::material::Material;
 use crate::ray::Ray;
 use crate::util::*;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// A hit represents a hit of a ray on a hittable
 pub struct Hit {
@@ -9,7 +10,7 @@ pub struct Hit {
     pub normal: Vec3,
     pub t: f64,
     pub front_face: bool,
-    pub material: Rc<dyn Material>,
+    pub material: Arc<dyn Material>,
 }
 
 impl Hit {
@@ -19,7 +20,7 @@ impl Hit {
         t: f64,
         r: &Ray,
         outward_normal: Vec3,
-        material: Rc<dyn Material>,
+        material: Arc<dyn Material>,
     ) -> Hit {
         let front_face = r.direction.dot(&outward_normal) < 0.0;
         let mut normal = outward_normal;
@@ -36,53 +37,117 @@ impl Hit {
     }
 }
 
-/// A hittable is a thing that rays can hit
-pub trait Hittable {
+/// A hittable is a thing that rays can hit. `Send + Sync` so that scenes can
+/// be shared across the render worker threads.
+pub trait Hittable: Send + Sync {
     fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<Hit>;
+
+    /// The axis-aligned bounding box containing this hittable, or None if it
+    /// has no well-defined bounds.
+    fn bounding_box(&self) -> Option<Aabb>;
 }
 
 // --- Hittables
 
+/// Intersect a ray with a sphere of the given center/radius, the shared
+/// quadratic solve used by both `Sphere` and `MovingSphere`.
+fn sphere_hit(
+    center: Point3,
+    radius: f64,
+    material: &Arc<dyn Material>,
+    r: &Ray,
+    t_min: f64,
+    t_max: f64,
+) -> Option<Hit> {
+    let oc = r.origin - center;
+    let a = length_squared(&r.direction);
+    let half_b = oc.dot(&r.direction);
+    let c = length_squared(&oc) - radius * radius;
+    let discriminant = half_b * half_b - a * c;
+
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let sqrtd = discriminant.sqrt();
+
+    // find the root that is within t_min..t_max
+    let mut t;
+    t = (-half_b - sqrtd) / a;
+    if t < t_min || t_max < t {
+        t = (-half_b + sqrtd) / a;
+        if t < t_min || t_max < t {
+            return None;
+        }
+    }
+
+    let p = r.at(t);
+    let outward_normal = (p - center) / radius;
+
+    Some(Hit::new_with_front_face(
+        p,
+        t,
+        r,
+        outward_normal,
+        material.clone(),
+    ))
+}
+
 pub struct Sphere {
     pub center: Point3,
     pub radius: f64,
-    pub material: Rc<dyn Material>,
+    pub material: Arc<dyn Material>,
 }
 
 impl Hittable for Sphere {
     fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<Hit> {
-        let oc = r.origin - self.center;
-        let a = length_squared(&r.direction);
-        let half_b = oc.dot(&r.direction);
-        let c = length_squared(&oc) - self.radius * self.radius;
-        let discriminant = half_b * half_b - a * c;
-
-        if discriminant < 0.0 {
-            return None;
-        }
+        sphere_hit(self.center, self.radius, &self.material, r, t_min, t_max)
+    }
 
-        let sqrtd = discriminant.sqrt();
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vec3::new(self.radius, self.radius, self.radius);
+        Some(Aabb::new(self.center - radius, self.center + radius))
+    }
+}
 
-        // find the root that is within t_min..t_max
-        let mut t;
-        t = (-half_b - sqrtd) / a;
-        if t < t_min || t_max < t {
-            t = -half_b + sqrtd;
-            if t < t_min || t_max < t {
-                return None;
-            }
-        }
+/// A sphere whose center moves linearly between `center0` at `time0` and
+/// `center1` at `time1`, producing motion blur when sampled over a shutter
+/// interval.
+pub struct MovingSphere {
+    pub center0: Point3,
+    pub center1: Point3,
+    pub time0: f64,
+    pub time1: f64,
+    pub radius: f64,
+    pub material: Arc<dyn Material>,
+}
 
-        let p = r.at(t);
-        let outward_normal = (p - self.center) / self.radius;
+impl MovingSphere {
+    /// The sphere's center at the given time, linearly interpolated between
+    /// `center0` and `center1`.
+    pub fn center(&self, time: f64) -> Point3 {
+        self.center0
+            + ((time - self.time0) / (self.time1 - self.time0)) * (self.center1 - self.center0)
+    }
+}
 
-        Some(Hit::new_with_front_face(
-            p,
-            t,
+impl Hittable for MovingSphere {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<Hit> {
+        sphere_hit(
+            self.center(r.time),
+            self.radius,
+            &self.material,
             r,
-            outward_normal,
-            self.material.clone(),
-        ))
+            t_min,
+            t_max,
+        )
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vec3::new(self.radius, self.radius, self.radius);
+        let box0 = Aabb::new(self.center0 - radius, self.center0 + radius);
+        let box1 = Aabb::new(self.center1 - radius, self.center1 + radius);
+        Some(box0.surrounding_box(&box1))
     }
 }
 
@@ -103,6 +168,12 @@ impl Hittables {
     pub fn add(&mut self, hittable: Box<dyn Hittable>) {
         self.hittables.push(hittable);
     }
+
+    /// Consume this collection, returning its objects for use in building an
+    /// acceleration structure such as a `BvhNode`.
+    pub fn into_vec(self) -> Vec<Box<dyn Hittable>> {
+        self.hittables
+    }
 }
 
 impl Hittable for Hittables {
@@ -118,4 +189,11 @@ impl Hittable for Hittables {
         }
         hit
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.hittables
+            .iter()
+            .filter_map(|h| h.bounding_box())
+            .reduce(|a, b| a.surrounding_box(&b))
+    }
 }
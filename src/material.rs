@@ -2,12 +2,19 @@ use crate::hit::Hit;
 use crate::ray::Ray;
 use crate::util::*;
 
-/// Material properties
-pub trait Material {
+/// Material properties. `Send + Sync` so that materials can be shared across
+/// the render worker threads.
+pub trait Material: Send + Sync {
     /// scatter the given ray with the given hit, returning
     /// a pair (attenuation, scattered), or None if the ray
     /// was absorbed.
     fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Color, Ray)>;
+
+    /// Light emitted by this material, independent of any incoming ray.
+    /// Defaults to no emission.
+    fn emitted(&self) -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
 }
 
 pub struct MatteFinish {
@@ -15,12 +22,12 @@ pub struct MatteFinish {
 }
 
 impl Material for MatteFinish {
-    fn scatter(&self, _ray: &Ray, hit: &Hit) -> Option<(Color, Ray)> {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Color, Ray)> {
         let mut scatter_dir = hit.normal + random_on_unit_sphere();
         if near_zero(scatter_dir) {
             scatter_dir = hit.normal;
         }
-        let scatter = Ray::new(hit.p, scatter_dir);
+        let scatter = Ray::new(hit.p, scatter_dir, ray.time);
         Some((self.albedo, scatter))
     }
 }
@@ -34,7 +41,11 @@ pub struct MetallicFinish {
 impl Material for MetallicFinish {
     fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Color, Ray)> {
         let reflected = reflect(unit_vector(&ray.direction), hit.normal);
-        let scatter = Ray::new(hit.p, reflected + self.fuzz * random_in_unit_sphere());
+        let scatter = Ray::new(
+            hit.p,
+            reflected + self.fuzz * random_in_unit_sphere(),
+            ray.time,
+        );
         Some((self.albedo, scatter))
     }
 }
@@ -70,7 +81,23 @@ impl Material for Refractive {
         } else {
             refract(unit_direction, hit.normal, refraction_ratio)
         };
-        let scattered = Ray::new(hit.p, direction);
+        let scattered = Ray::new(hit.p, direction, ray.time);
         Some((Color::new(1., 1., 1.), scattered))
     }
 }
+
+/// A material that emits light rather than scattering it, e.g. a glowing
+/// sphere used to illuminate a scene.
+pub struct DiffuseLight {
+    pub emit: Color,
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _ray: &Ray, _hit: &Hit) -> Option<(Color, Ray)> {
+        None
+    }
+
+    fn emitted(&self) -> Color {
+        self.emit
+    }
+}
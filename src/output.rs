@@ -0,0 +1,77 @@
+use crate::util::Color;
+use anyhow::{bail, Result};
+use geefr_ppm::Ppm;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// An in-progress rendered image, written out as PPM or PNG depending on the
+/// output filename's extension.
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0, 0, 0]; width * height],
+        }
+    }
+
+    /// Set a pixel from a linear color, clamping each component to `[0, 1]`
+    /// before scaling to a byte so saturated highlights don't wrap around.
+    pub fn set_pixel(&mut self, x: usize, y: usize, c: Color) {
+        let to_byte = |v: f64| (v.clamp(0.0, 1.0) * 255.999) as u8;
+        self.pixels[y * self.width + x] = [to_byte(c.x), to_byte(c.y), to_byte(c.z)];
+    }
+
+    /// Write this image to `path`. The file extension (`.png` or `.ppm`)
+    /// selects the encoder; unrecognized extensions are an error.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => self.write_png(path),
+            Some("ppm") | None => self.write_ppm(path),
+            Some(ext) => bail!("unsupported output format: .{}", ext),
+        }
+    }
+
+    fn write_ppm(&self, path: &Path) -> Result<()> {
+        let mut ppm = Ppm::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let [r, g, b] = self.pixels[y * self.width + x];
+                ppm.set_pixel(x, y, r, g, b);
+            }
+        }
+        Ok(ppm.write(path.to_string_lossy().into_owned())?)
+    }
+
+    fn write_png(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)?;
+        let writer = BufWriter::new(file);
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+        writer.write_image_data(&data)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn set_pixel_clamps_out_of_range_components() {
+        let mut image = Image::new(1, 1);
+        image.set_pixel(0, 0, Color::new(-1.0, 0.5, 2.0));
+        assert_eq!(image.pixels[0], [0, 127, 255]);
+    }
+}
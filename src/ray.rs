@@ -3,11 +3,17 @@ use crate::util::*;
 pub struct Ray {
     pub origin: Point3,
     pub direction: Point3,
+    /// The time at which this ray was cast, used to sample moving geometry.
+    pub time: f64,
 }
 
 impl Ray {
-    pub fn new(origin: Point3, direction: Vec3) -> Self {
-        Self { origin, direction }
+    pub fn new(origin: Point3, direction: Vec3, time: f64) -> Self {
+        Self {
+            origin,
+            direction,
+            time,
+        }
     }
 
     pub fn at(&self, t: f64) -> Vec3 {
@@ -0,0 +1,123 @@
+use crate::camera::Camera;
+use crate::hit::Hittable;
+use crate::output::Image;
+use crate::ray::Ray;
+use crate::util::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Base seed for per-tile RNGs; each tile's thread seeds from
+/// `BASE_SEED + tile index` so renders are reproducible across runs
+/// regardless of how tiles are scheduled onto OS threads.
+const BASE_SEED: u64 = 0xc0ffee;
+
+fn ray_color<H: Hittable>(
+    world: &H,
+    r: &Ray,
+    recurse_depth: usize,
+    background: Option<Color>,
+) -> Color {
+    if recurse_depth == 0 {
+        return Color::new(0., 0., 0.);
+    }
+
+    if let Some(hit) = world.hit(r, 0.001, f64::INFINITY) {
+        let emitted = hit.material.emitted();
+        return match hit.material.scatter(r, &hit) {
+            Some((attenuation, scattered)) => {
+                let rc = ray_color(world, &scattered, recurse_depth - 1, background);
+                emitted + component_mult(rc, attenuation)
+            }
+            None => emitted,
+        };
+    }
+
+    match background {
+        Some(color) => color,
+        None => {
+            // draw the sky gradient
+            let unit_direction = unit_vector(&r.direction);
+            let t = 0.5 * (unit_direction.y + 1.0);
+            let white: Color = Color::new(1.0, 1.0, 1.0);
+            let bluish: Color = Color::new(0.5, 0.5, 1.0);
+            white * (1.0 - t) + bluish * t
+        }
+    }
+}
+
+/// The row range `[y_start, y_end)` handled by one worker thread.
+fn row_bands(height: usize, num_threads: usize) -> Vec<(usize, usize)> {
+    let band_height = height.div_ceil(num_threads);
+    let mut bands = Vec::new();
+    let mut y = 0;
+    while y < height {
+        let y_end = (y + band_height).min(height);
+        bands.push((y, y_end));
+        y = y_end;
+    }
+    bands
+}
+
+/// Render `world` as seen through `camera`, splitting the image into row
+/// bands and tracing them in parallel across `num_threads` worker threads.
+/// Each thread samples its own rows independently and the resulting tiles
+/// are composited into a single `Image` once all threads finish. Rays that
+/// escape the scene are colored `background`, or the default sky gradient
+/// when `None`.
+#[allow(clippy::too_many_arguments)]
+pub fn render<H: Hittable>(
+    world: &H,
+    camera: &Camera,
+    width: usize,
+    height: usize,
+    samples_per_pixel: usize,
+    max_recursion: usize,
+    num_threads: usize,
+    background: Option<Color>,
+) -> Image {
+    let mut image = Image::new(width, height);
+    let rows_done = AtomicUsize::new(0);
+
+    let tiles = thread::scope(|scope| {
+        let handles: Vec<_> = row_bands(height, num_threads)
+            .into_iter()
+            .enumerate()
+            .map(|(tile_index, (y_start, y_end))| {
+                let rows_done = &rows_done;
+                scope.spawn(move || {
+                    seed_thread_rng(BASE_SEED.wrapping_add(tile_index as u64));
+                    let mut tile = Vec::with_capacity((y_end - y_start) * width);
+                    for y in y_start..y_end {
+                        for x in 0..width {
+                            let mut color = Color::new(0.0, 0.0, 0.0);
+                            for _ in 0..samples_per_pixel {
+                                let u = (x as f64 + uniform()) / width as f64;
+                                let v = (y as f64 + uniform()) / height as f64;
+                                let r = camera.get_ray(u, v);
+                                color += ray_color(world, &r, max_recursion, background);
+                            }
+                            color = (color / (samples_per_pixel as f64)).map(|c| c.sqrt());
+                            tile.push((x, y, color));
+                        }
+                        let done = rows_done.fetch_add(1, Ordering::Relaxed) + 1;
+                        println!("{}% finished ({} rows of {})", done * 100 / height, done, height);
+                    }
+                    tile
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    for tile in tiles {
+        for (x, y, color) in tile {
+            image.set_pixel(x, height - y - 1, color);
+        }
+    }
+
+    image
+}
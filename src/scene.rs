@@ -0,0 +1,146 @@
+use crate::camera::Camera;
+use crate::hit::{Hittables, MovingSphere, Sphere};
+use crate::material::{DiffuseLight, Material, MatteFinish, MetallicFinish, Refractive};
+use crate::util::*;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Camera parameters as read from a scene file.
+#[derive(Deserialize)]
+pub struct CameraDesc {
+    pub lookfrom: [f64; 3],
+    pub lookat: [f64; 3],
+    pub vup: [f64; 3],
+    pub vfov_degrees: f64,
+    pub aspect_ratio: f64,
+    pub aperture: f64,
+    pub time0: f64,
+    pub time1: f64,
+}
+
+impl CameraDesc {
+    fn build(&self) -> Camera {
+        Camera::new(
+            Point3::new(self.lookfrom[0], self.lookfrom[1], self.lookfrom[2]),
+            Point3::new(self.lookat[0], self.lookat[1], self.lookat[2]),
+            Vec3::new(self.vup[0], self.vup[1], self.vup[2]),
+            self.vfov_degrees.to_radians(),
+            self.aspect_ratio,
+            self.aperture,
+            self.time0,
+            self.time1,
+        )
+    }
+}
+
+/// A material as read from a scene file.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MaterialDesc {
+    Matte { albedo: [f64; 3] },
+    Metal { albedo: [f64; 3], fuzz: f64 },
+    Glass { ir: f64 },
+    Light { emit: [f64; 3] },
+}
+
+impl MaterialDesc {
+    fn build(&self) -> Arc<dyn Material> {
+        match self {
+            MaterialDesc::Matte { albedo } => Arc::new(MatteFinish {
+                albedo: Color::new(albedo[0], albedo[1], albedo[2]),
+            }),
+            MaterialDesc::Metal { albedo, fuzz } => Arc::new(MetallicFinish {
+                albedo: Color::new(albedo[0], albedo[1], albedo[2]),
+                fuzz: *fuzz,
+            }),
+            MaterialDesc::Glass { ir } => Arc::new(Refractive { ir: *ir }),
+            MaterialDesc::Light { emit } => Arc::new(DiffuseLight {
+                emit: Color::new(emit[0], emit[1], emit[2]),
+            }),
+        }
+    }
+}
+
+/// A sphere as read from a scene file. `center1` is only needed for a
+/// moving sphere; when absent the sphere is stationary.
+#[derive(Deserialize)]
+pub struct SphereDesc {
+    pub center: [f64; 3],
+    pub center1: Option<[f64; 3]>,
+    pub radius: f64,
+    pub material: MaterialDesc,
+}
+
+/// A complete scene description: resolution, sample counts, a camera, and
+/// the list of primitives to render.
+#[derive(Deserialize)]
+pub struct Scene {
+    pub width: usize,
+    pub height: usize,
+    pub samples_per_pixel: usize,
+    pub max_recursion: usize,
+    pub camera: CameraDesc,
+    /// Constant background color, replacing the default sky gradient when
+    /// present. Useful for scenes lit only by `DiffuseLight` materials.
+    #[serde(default)]
+    pub background: Option<[f64; 3]>,
+    pub spheres: Vec<SphereDesc>,
+}
+
+impl Scene {
+    /// Load a scene description from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Scene> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read scene file {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse scene file {}", path.display()))
+    }
+
+    /// The background color described by this scene, or `None` to fall back
+    /// to the renderer's default sky gradient.
+    pub fn background_color(&self) -> Option<Color> {
+        self.background.map(|b| Color::new(b[0], b[1], b[2]))
+    }
+
+    /// Build the world of hittables and the camera described by this scene.
+    pub fn build(&self) -> (Hittables, Camera) {
+        let mut hittables = Hittables::default();
+        for sphere in &self.spheres {
+            let material = sphere.material.build();
+            let center = Point3::new(sphere.center[0], sphere.center[1], sphere.center[2]);
+            match sphere.center1 {
+                Some(center1) => hittables.add(Box::new(MovingSphere {
+                    center0: center,
+                    center1: Point3::new(center1[0], center1[1], center1[2]),
+                    time0: self.camera.time0,
+                    time1: self.camera.time1,
+                    radius: sphere.radius,
+                    material,
+                })),
+                None => hittables.add(Box::new(Sphere {
+                    center,
+                    radius: sphere.radius,
+                    material,
+                })),
+            }
+        }
+        (hittables, self.camera.build())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn load_round_trips_demo_scene() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/scenes/demo.json");
+        let scene = Scene::load(path).expect("demo scene should parse");
+        assert_eq!(scene.spheres.len(), 5);
+        assert_eq!(scene.background_color(), Some(Color::new(0.0, 0.0, 0.0)));
+    }
+}
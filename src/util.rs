@@ -1,13 +1,27 @@
-use geefr_ppm::Ppm;
 use lazy_static::lazy_static;
 use nalgebra::Vector3;
 use rand::distributions::Uniform;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
 
 lazy_static! {
     static ref UNIFORM: Uniform<f64> = Uniform::new(0.0, 1.0);
 }
 
+thread_local! {
+    // Seeded from entropy by default so single-threaded callers (e.g. tests)
+    // still get varied output; `seed_thread_rng` overrides this per thread.
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Reseed this thread's RNG. Render worker threads call this with a
+/// per-tile seed so that a given tile's samples are reproducible across
+/// runs, independent of which OS thread happens to draw it.
+pub fn seed_thread_rng(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
 pub type Vec3 = Vector3<f64>;
 pub type Point3 = Vec3;
 pub type Color = Vec3;
@@ -29,17 +43,6 @@ pub fn unit_vector(v: &Vec3) -> Vec3 {
     v / length(v)
 }
 
-/// Set a pixel in a PPM document based on this color
-pub fn set_pixel(ppm: &mut Ppm, x: usize, y: usize, c: Color) {
-    ppm.set_pixel(
-        x,
-        y,
-        (c.x * 256.0) as u8,
-        (c.y * 256.0) as u8,
-        (c.z * 256.0) as u8,
-    )
-}
-
 pub fn near_zero(v: Vec3) -> bool {
     v.x < 1e-8 && v.y < 1e-8 && v.z < 1e-8
 }
@@ -65,7 +68,7 @@ pub fn refract(v: Vec3, normal: Vec3, ratio: f64) -> Vec3 {
 
 /// Return a value uniformly sampled between 0 and 1
 pub fn uniform() -> f64 {
-    thread_rng().sample(*UNIFORM)
+    RNG.with(|rng| rng.borrow_mut().sample(*UNIFORM))
 }
 
 /// Return a random point in the unit sphere
@@ -108,6 +111,8 @@ pub fn random_color() -> Color {
 
 pub fn random_color_range(min: f64, max: f64) -> Color {
     let uni = Uniform::new(min, max);
-    let mut rng = thread_rng();
-    Color::new(rng.sample(uni), rng.sample(uni), rng.sample(uni))
+    RNG.with(|rng| {
+        let mut rng = rng.borrow_mut();
+        Color::new(rng.sample(uni), rng.sample(uni), rng.sample(uni))
+    })
 }